@@ -1,11 +1,34 @@
 use anyhow::Result;
+use futures::StreamExt;
 use rig::client::{CompletionClient, ProviderClient};
 use rig::completion::Prompt;
 use rig::providers::ollama;
-use tracing::{debug, info};
+use rig::streaming::{StreamingChoice, StreamingPrompt};
+use serde::Deserialize;
+use serde_json::json;
+use std::io::{self, Write};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
 
 use crate::config::Config;
-use crate::tools::WebSearchTool;
+use crate::retrieval::{chunk_text, EmbeddedChunk, InMemoryVectorStore, OllamaEmbeddingClient, VectorStore};
+use crate::session::SessionStore;
+use crate::tools::{PageFetchTool, WebSearchTool};
+
+/// How many top-ranked chunks to inject into the prompt as retrieved context.
+const TOP_K_CHUNKS: usize = 5;
+
+/// Shape of Ollama's `GET /api/tags` response.
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    models: Vec<TagEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagEntry {
+    name: String,
+}
 
 // =============================================================================
 // SYSTEM PROMPT
@@ -36,18 +59,21 @@ const CHAT_SYSTEM_PROMPT: &str = r#"
 You are an AI research assistant. You help users by searching the web and summarizing findings.
 
 SEARCH RULES (CRITICAL - FOLLOW EXACTLY):
-1. You have access to a web_search tool
-2. Search ONCE only - do not repeat searches
-3. After the search completes, you MUST provide your final answer directly
-4. Stop after one search - do NOT call web_search again
-5. Your response should include sources (URLs)
+1. You have access to a web_search tool and a fetch_page tool
+2. Relevant passages retrieved from the top search results are already provided below - prefer these
+3. Only call web_search/fetch_page yourself if the retrieved context is insufficient (fetch at most {max_fetch} pages)
+4. After answering, your response should include sources (URLs), drawn from the retrieved context when possible
+5. Stop after answering - do not keep calling tools once you have enough to respond
+
+RETRIEVED CONTEXT (top matching passages for this question):
+{context}
 
 CONVERSATION HISTORY:
 {history}
 
 When the user asks a question:
-- Search once using web_search
-- After receiving results, give a complete answer with sources
+- Ground your answer in the retrieved context above, citing source URLs
+- Only search/fetch yourself if the context above doesn't cover the question
 - Do not ask follow-up questions or call tools again
 "#;
 
@@ -58,19 +84,204 @@ pub struct ResearchAgent {
     /// The web search tool
     search_tool: WebSearchTool,
 
-    /// Conversation history: (user_question, ai_response)
+    /// The full-text page fetch tool
+    fetch_tool: PageFetchTool,
+
+    /// Client used to embed chunks/queries for RAG retrieval
+    embedding_client: OllamaEmbeddingClient,
+
+    /// Shared HTTP client for plain Ollama REST calls (e.g. `/api/tags`)
+    http_client: reqwest::Client,
+
+    /// Conversation history for the current session: (user_question, ai_response)
     history: Vec<(String, String)>,
+
+    /// SQLite-backed store that persists `history` across process restarts
+    session_store: SessionStore,
+
+    /// The session this agent is attached to; turns are appended here
+    session_id: String,
+
+    /// Index of the next turn to write, continuing on from any turns
+    /// already loaded from the session store
+    next_turn_index: usize,
 }
 
 impl ResearchAgent {
-    pub fn new(config: Config) -> Self {
-        let search_tool = WebSearchTool::new(config.max_search_results);
+    /// Create a new agent attached to a session.
+    ///
+    /// If `session_id` is `Some`, that session is resumed (or created, if
+    /// it doesn't exist yet) and its prior turns are loaded so the
+    /// `{history}` block in `CHAT_SYSTEM_PROMPT` picks up where it left
+    /// off. If `None` and `resume` is set, the most recently created stored
+    /// session is resumed; otherwise a fresh session id is generated. The session row
+    /// itself isn't written here - `sessions`/`models` construct an agent
+    /// just to read metadata and must not leave a phantom row behind, so
+    /// creation is deferred to `chat`/`chat_stream`'s first real turn.
+    pub fn new(config: Config, session_id: Option<String>, resume: bool) -> Result<Self> {
+        let search_tool =
+            WebSearchTool::new(config.max_search_results, config.request_timeout_secs);
+        let fetch_tool = PageFetchTool::new(config.request_timeout_secs);
+        let embedding_client = OllamaEmbeddingClient::new(
+            config.ollama_host.clone(),
+            config.embedding_model.clone(),
+            config.request_timeout_secs,
+        );
+        let session_store = SessionStore::open(&config.session_db_path)?;
+
+        let session_id = match session_id {
+            Some(id) => id,
+            None if resume => session_store
+                .latest_session_id()?
+                .unwrap_or_else(|| Uuid::new_v4().to_string()),
+            None => Uuid::new_v4().to_string(),
+        };
 
-        Self {
+        let history = session_store.load_turns(&session_id)?;
+        let next_turn_index = history.len();
+
+        info!(session_id = %session_id, turns = history.len(), "Session ready");
+
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()
+            .expect("building the HTTP client should not fail");
+
+        Ok(Self {
             config,
             search_tool,
-            history: Vec::new(),
+            fetch_tool,
+            embedding_client,
+            http_client,
+            history,
+            session_store,
+            session_id,
+            next_turn_index,
+        })
+    }
+
+    /// The id of the session this agent is currently writing to.
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// The Ollama host this agent talks to.
+    pub fn ollama_host(&self) -> &str {
+        &self.config.ollama_host
+    }
+
+    /// The model this agent is configured to use.
+    pub fn config_model(&self) -> &str {
+        &self.config.model
+    }
+
+    /// List the models currently available on the configured Ollama server
+    /// by calling its `/api/tags` endpoint. Ollama exposes no dedicated
+    /// health-check API, so this also doubles as the connectivity probe.
+    pub async fn list_models(&self) -> Result<Vec<String>> {
+        let url = format!("{}/api/tags", self.config.ollama_host.trim_end_matches('/'));
+
+        let response = self.http_client.get(&url).send().await.map_err(|e| {
+            anyhow::anyhow!(
+                "Could not reach Ollama at {}: {}",
+                self.config.ollama_host,
+                e
+            )
+        })?;
+        let response = response
+            .error_for_status()
+            .map_err(|e| anyhow::anyhow!("Ollama returned an error: {}", e))?;
+
+        let body: TagsResponse = response.json().await?;
+        Ok(body.models.into_iter().map(|m| m.name).collect())
+    }
+
+    /// Confirm the Ollama server is reachable and that the configured model
+    /// is among the locally installed ones, returning an actionable error
+    /// (`ollama pull <model>`) if not.
+    pub async fn check_connection(&self) -> Result<()> {
+        let models = self.list_models().await?;
+
+        // Ollama tags come back as "name:tag" (e.g. "llama3.2:latest"), so
+        // match on either the full tag or the bare model name.
+        let installed = models.iter().any(|m| {
+            m == &self.config.model || m.split(':').next() == Some(self.config.model.as_str())
+        });
+
+        if !installed {
+            anyhow::bail!(
+                "Model '{}' is not installed on {}. Run: ollama pull {}",
+                self.config.model,
+                self.config.ollama_host,
+                self.config.model
+            );
         }
+
+        Ok(())
+    }
+
+    /// List all sessions stored in the session database.
+    pub fn list_sessions(&self) -> Result<Vec<crate::session::SessionSummary>> {
+        self.session_store.list_sessions()
+    }
+
+    /// Derive a short session title from a session's first query, so the
+    /// `sessions` listing shows something more useful than a placeholder.
+    fn derive_title(query: &str) -> String {
+        const MAX_TITLE_CHARS: usize = 60;
+
+        let trimmed = query.trim();
+        if trimmed.chars().count() <= MAX_TITLE_CHARS {
+            trimmed.to_string()
+        } else {
+            let truncated: String = trimmed.chars().take(MAX_TITLE_CHARS).collect();
+            format!("{}...", truncated.trim_end())
+        }
+    }
+
+    /// Search, fetch the top results, and rank their chunks against `query`
+    /// by embedding similarity. Never fails the caller: retrieval errors
+    /// (no network, Ollama unreachable, etc.) degrade to an empty context
+    /// so `chat` can still fall back to the model's own tool calls.
+    async fn retrieve_context(&self, query: &str) -> String {
+        match self.build_context(query).await {
+            Ok(context) => context,
+            Err(e) => {
+                warn!(error = %e, "RAG retrieval failed; continuing without pre-fetched context");
+                "No additional context retrieved.".to_string()
+            }
+        }
+    }
+
+    async fn build_context(&self, query: &str) -> Result<String> {
+        let results = self.search_tool.search(query).await?;
+        let urls: Vec<String> = results.into_iter().map(|r| r.url).collect();
+        let pages = self.fetch_tool.fetch_substantive(&urls, self.config.max_fetch).await;
+
+        let mut store = InMemoryVectorStore::new();
+        for page in &pages {
+            for chunk in chunk_text(&page.text) {
+                let embedding = self.embedding_client.embed(&chunk).await?;
+                store.add(EmbeddedChunk {
+                    text: chunk,
+                    source_url: page.url.clone(),
+                    embedding,
+                });
+            }
+        }
+
+        let query_embedding = self.embedding_client.embed(query).await?;
+        let top_chunks = store.top_k(&query_embedding, TOP_K_CHUNKS);
+
+        if top_chunks.is_empty() {
+            return Ok("No additional context retrieved.".to_string());
+        }
+
+        Ok(top_chunks
+            .iter()
+            .map(|c| format!("[Source: {}]\n{}", c.source_url, c.text))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
     }
 
     /// Chat with the agent, maintaining conversation history
@@ -99,13 +310,29 @@ impl ResearchAgent {
                 .join("\n\n")
         };
 
-        // Replace {history} in the system prompt
-        let system_prompt = CHAT_SYSTEM_PROMPT.replace("{history}", &history_str);
+        // Retrieve the passages most relevant to this query before we ever
+        // build the prompt, so the model can ground its answer in them
+        // instead of dumping whole pages into context.
+        let context = self.retrieve_context(query).await;
+
+        // Replace {history}, {max_fetch} and {context} in the system prompt
+        let system_prompt = CHAT_SYSTEM_PROMPT
+            .replace("{history}", &history_str)
+            .replace("{max_fetch}", &self.config.max_fetch.to_string())
+            .replace("{context}", &context);
 
         let agent = ollama_client
             .agent(&self.config.model)
             .preamble(&system_prompt)
+            .temperature(self.config.temperature as f64)
+            .additional_params(json!({
+                "options": {
+                    "num_ctx": self.config.num_ctx,
+                    "top_p": self.config.top_p,
+                }
+            }))
             .tool(self.search_tool.clone())
+            .tool(self.fetch_tool.clone())
             .build();
 
         info!("Agent configured, executing chat query");
@@ -116,13 +343,37 @@ impl ResearchAgent {
             query
         );
 
-        let response = agent
-            .prompt(&enhanced_query)
-            .multi_turn(5)
-            .await
-            .map_err(|e| anyhow::anyhow!("Agent execution failed: {}", e))?;
+        let response = tokio::time::timeout(
+            Duration::from_secs(self.config.request_timeout_secs),
+            agent.prompt(&enhanced_query).multi_turn(5),
+        )
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Ollama request timed out after {}s (a slow first-token model load can exceed this - try raising --timeout)",
+                self.config.request_timeout_secs
+            )
+        })?
+        .map_err(|e| anyhow::anyhow!("Agent execution failed: {}", e))?;
+
+        // This is the first real turn for this session: create its row now
+        // (rather than at construction time) so `sessions`/`models`, which
+        // also construct an agent but never chat, never leave a phantom
+        // row behind - and give it a title derived from the query instead
+        // of a placeholder.
+        if self.next_turn_index == 0 {
+            self.session_store.ensure_session(
+                &self.session_id,
+                &Self::derive_title(query),
+                &self.config.model,
+            )?;
+        }
 
-        // Save to history
+        // Persist the turn before it's reflected in the in-memory view, so a
+        // failed write never leaves `history` holding a turn the DB lacks.
+        self.session_store
+            .append_turn(&self.session_id, self.next_turn_index, query, &response)?;
+        self.next_turn_index += 1;
         self.history.push((query.to_string(), response.clone()));
 
         info!("Chat completed successfully");
@@ -130,7 +381,137 @@ impl ResearchAgent {
         Ok(response)
     }
 
-    /// Clear conversation history
+    /// Like `chat`, but streams tokens to stdout as they arrive instead of
+    /// buffering the full response. Uses the same `multi_turn(5)` semantics
+    /// as `chat` so a `web_search`/`fetch_page` call mid-stream is actually
+    /// dispatched and the stream continues with the model's follow-up,
+    /// rather than truncating at the tool call. When `verbose` is set,
+    /// intermediate tool calls (the search query issued, the URLs fetched)
+    /// are also printed as they happen, analogous to exposing
+    /// `intermediate_steps` on a retrieval agent. `request_timeout_secs`
+    /// bounds the whole exchange, not just the initial connection, so a
+    /// stall mid-stream still times out.
+    pub async fn chat_stream(&mut self, query: &str, verbose: bool) -> Result<String> {
+        info!(query = %query, "Starting streaming chat query");
+
+        std::env::set_var("OLLAMA_API_BASE_URL", &self.config.ollama_host);
+
+        let ollama_client = ollama::Client::from_env();
+
+        debug!(
+            host = %self.config.ollama_host,
+            model = %self.config.model,
+            "Connected to Ollama"
+        );
+
+        let history_str = if self.history.is_empty() {
+            "No previous conversation.".to_string()
+        } else {
+            self.history
+                .iter()
+                .enumerate()
+                .map(|(i, (q, a))| format!("[Turn {}]\nUser: {}\nAI: {}", i + 1, q, a))
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        };
+
+        let context = self.retrieve_context(query).await;
+
+        let system_prompt = CHAT_SYSTEM_PROMPT
+            .replace("{history}", &history_str)
+            .replace("{max_fetch}", &self.config.max_fetch.to_string())
+            .replace("{context}", &context);
+
+        let agent = ollama_client
+            .agent(&self.config.model)
+            .preamble(&system_prompt)
+            .temperature(self.config.temperature as f64)
+            .additional_params(json!({
+                "options": {
+                    "num_ctx": self.config.num_ctx,
+                    "top_p": self.config.top_p,
+                }
+            }))
+            .tool(self.search_tool.clone())
+            .tool(self.fetch_tool.clone())
+            .build();
+
+        let enhanced_query = format!(
+            "Research and answer the following question. Use the web_search tool to find \
+             current information, then provide a comprehensive summary with sources:\n\n{}",
+            query
+        );
+
+        // Bound the whole exchange - stream construction *and* consumption -
+        // in one timeout, so a stall mid-stream (e.g. the model pausing to
+        // run a tool) still gets caught, not just a slow initial connection.
+        let response = tokio::time::timeout(
+            Duration::from_secs(self.config.request_timeout_secs),
+            async {
+                // `multi_turn(5)` mirrors `chat`: it actually dispatches a
+                // `web_search`/`fetch_page` call the model makes mid-stream
+                // and feeds the tool result back in, rather than the stream
+                // just ending at the tool call.
+                let mut stream = agent
+                    .stream_prompt(&enhanced_query)
+                    .multi_turn(5)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Agent execution failed: {}", e))?;
+
+                let mut response = String::new();
+                while let Some(chunk) = stream.next().await {
+                    match chunk.map_err(|e| anyhow::anyhow!("Streaming failed: {}", e))? {
+                        StreamingChoice::Message(text) => {
+                            print!("{}", text);
+                            io::stdout().flush()?;
+                            response.push_str(&text);
+                        }
+                        StreamingChoice::ToolCall(name, _id, args) => {
+                            if verbose {
+                                eprintln!("\n[tool] {} called with {}", name, args);
+                            }
+                        }
+                    }
+                }
+                println!();
+
+                Ok::<String, anyhow::Error>(response)
+            },
+        )
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Ollama request timed out after {}s (a slow first-token model load can exceed this - try raising --timeout)",
+                self.config.request_timeout_secs
+            )
+        })??;
+
+        // This is the first real turn for this session: create its row now
+        // (rather than at construction time) so `sessions`/`models`, which
+        // also construct an agent but never chat, never leave a phantom
+        // row behind - and give it a title derived from the query instead
+        // of a placeholder.
+        if self.next_turn_index == 0 {
+            self.session_store.ensure_session(
+                &self.session_id,
+                &Self::derive_title(query),
+                &self.config.model,
+            )?;
+        }
+
+        self.session_store
+            .append_turn(&self.session_id, self.next_turn_index, query, &response)?;
+        self.next_turn_index += 1;
+        self.history.push((query.to_string(), response.clone()));
+
+        info!("Streaming chat completed successfully");
+
+        Ok(response)
+    }
+
+    /// Clear the in-memory conversation view. The turns already written to
+    /// the session database are left intact and can still be resumed later
+    /// via `--session <id> --resume`.
     pub fn clear_history(&mut self) {
         self.history.clear();
     }
@@ -158,7 +539,15 @@ impl ResearchAgent {
         let agent = ollama_client
             .agent(&self.config.model)
             .preamble(RESEARCH_SYSTEM_PROMPT)
+            .temperature(self.config.temperature as f64)
+            .additional_params(json!({
+                "options": {
+                    "num_ctx": self.config.num_ctx,
+                    "top_p": self.config.top_p,
+                }
+            }))
             .tool(self.search_tool.clone())
+            .tool(self.fetch_tool.clone())
             .build();
 
         info!("Agent configured, executing research query");
@@ -170,11 +559,18 @@ impl ResearchAgent {
             query
         );
 
-        let response = agent
-            .prompt(&enhanced_query)
-            .multi_turn(5) // Allow up to 5 iterations of tool calls
-            .await
-            .map_err(|e| anyhow::anyhow!("Agent execution failed: {}", e))?;
+        let response = tokio::time::timeout(
+            Duration::from_secs(self.config.request_timeout_secs),
+            agent.prompt(&enhanced_query).multi_turn(5), // Allow up to 5 iterations of tool calls
+        )
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Ollama request timed out after {}s (a slow first-token model load can exceed this - try raising --timeout)",
+                self.config.request_timeout_secs
+            )
+        })?
+        .map_err(|e| anyhow::anyhow!("Agent execution failed: {}", e))?;
 
         info!("Research completed successfully");
 
@@ -221,10 +617,15 @@ impl ResearchAgent {
 mod tests {
     use super::*;
 
+    fn test_config() -> Config {
+        let mut config = Config::default();
+        config.session_db_path = ":memory:".to_string();
+        config
+    }
+
     #[test]
     fn test_agent_creation() {
-        let config = Config::default();
-        let agent = ResearchAgent::new(config);
+        let agent = ResearchAgent::new(test_config(), None, false).unwrap();
 
         assert_eq!(agent.config.model, "llama3.2");
         assert!(agent.history.is_empty());
@@ -244,8 +645,7 @@ mod tests {
 
     #[test]
     fn test_clear_history() {
-        let config = Config::default();
-        let mut agent = ResearchAgent::new(config);
+        let mut agent = ResearchAgent::new(test_config(), None, false).unwrap();
 
         // Initially empty
         assert!(agent.history.is_empty());
@@ -261,9 +661,37 @@ mod tests {
 
     #[test]
     fn test_history_getter() {
-        let config = Config::default();
-        let agent = ResearchAgent::new(config);
+        let agent = ResearchAgent::new(test_config(), None, false).unwrap();
 
         assert_eq!(agent.history().len(), 0);
     }
+
+    #[test]
+    fn test_derive_title_short_query_unchanged() {
+        assert_eq!(ResearchAgent::derive_title("What is Rust?"), "What is Rust?");
+    }
+
+    #[test]
+    fn test_derive_title_truncates_long_query() {
+        let query = "a".repeat(100);
+        let title = ResearchAgent::derive_title(&query);
+        assert!(title.ends_with("..."));
+        assert!(title.len() < query.len());
+    }
+
+    #[test]
+    fn test_resume_loads_prior_turns() {
+        let config = test_config();
+        // Using a named in-memory db requires a shared cache to persist
+        // across connections; exercise the resume path via the store API
+        // directly instead, which is what `ResearchAgent::new` relies on.
+        let store = SessionStore::open(&config.session_db_path).unwrap();
+        store
+            .ensure_session("existing", "Resumed session", &config.model)
+            .unwrap();
+        store.append_turn("existing", 0, "q1", "a1").unwrap();
+
+        let turns = store.load_turns("existing").unwrap();
+        assert_eq!(turns, vec![("q1".to_string(), "a1".to_string())]);
+    }
 }
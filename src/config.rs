@@ -0,0 +1,219 @@
+use anyhow::{bail, Result};
+use std::env;
+
+/// Default Ollama host when `OLLAMA_HOST` is not set.
+const DEFAULT_OLLAMA_HOST: &str = "http://localhost:11434";
+
+/// Default model when `OLLAMA_MODEL` is not set.
+const DEFAULT_MODEL: &str = "llama3.2";
+
+/// Default number of results returned by the web search tool.
+const DEFAULT_MAX_SEARCH_RESULTS: usize = 5;
+
+/// Default path for the SQLite session store.
+const DEFAULT_SESSION_DB_PATH: &str = "sessions.db";
+
+/// Default number of search results to fetch full page content for.
+const DEFAULT_MAX_FETCH: usize = 3;
+
+/// Default Ollama model used to embed chunks for RAG retrieval.
+const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
+
+/// Default context window size. Local models silently truncate context that
+/// exceeds this, so we set it explicitly rather than rely on server defaults.
+const DEFAULT_NUM_CTX: u32 = 4096;
+
+/// Default sampling temperature.
+const DEFAULT_TEMPERATURE: f32 = 0.7;
+
+/// Default nucleus sampling threshold.
+const DEFAULT_TOP_P: f32 = 0.9;
+
+/// Default request timeout, generous enough to cover a slow first-token
+/// model load.
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// Runtime configuration, assembled from environment variables (and a
+/// `.env` file, if present) and then possibly overridden by CLI flags.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The Ollama model to use for chat/research.
+    pub model: String,
+
+    /// Base URL of the Ollama server.
+    pub ollama_host: String,
+
+    /// Max number of results to pull back from a web search.
+    pub max_search_results: usize,
+
+    /// Path to the SQLite database used to persist conversation sessions.
+    pub session_db_path: String,
+
+    /// Max number of search results to fetch the full page content for.
+    pub max_fetch: usize,
+
+    /// Ollama model used to embed chunks and queries for RAG retrieval.
+    pub embedding_model: String,
+
+    /// Context window size passed to Ollama as `num_ctx`.
+    pub num_ctx: u32,
+
+    /// Sampling temperature passed to Ollama.
+    pub temperature: f32,
+
+    /// Nucleus sampling threshold passed to Ollama as `top_p`.
+    pub top_p: f32,
+
+    /// How long to wait for a generation request before giving up.
+    pub request_timeout_secs: u64,
+}
+
+impl Config {
+    /// Build a `Config` from environment variables (and a `.env` file, if present).
+    pub fn from_env() -> Result<Self> {
+        // Loading a `.env` file is best-effort; it's fine if one doesn't exist.
+        let _ = dotenvy::dotenv();
+
+        let model = env::var("OLLAMA_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+        let ollama_host =
+            env::var("OLLAMA_HOST").unwrap_or_else(|_| DEFAULT_OLLAMA_HOST.to_string());
+        let max_search_results = env::var("MAX_SEARCH_RESULTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_SEARCH_RESULTS);
+        let session_db_path = env::var("SESSION_DB_PATH")
+            .unwrap_or_else(|_| DEFAULT_SESSION_DB_PATH.to_string());
+        let max_fetch = env::var("MAX_FETCH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_FETCH);
+        let embedding_model = env::var("EMBEDDING_MODEL")
+            .unwrap_or_else(|_| DEFAULT_EMBEDDING_MODEL.to_string());
+        let num_ctx = env::var("NUM_CTX")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_NUM_CTX);
+        let temperature = env::var("TEMPERATURE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_TEMPERATURE);
+        let top_p = env::var("TOP_P")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_TOP_P);
+        let request_timeout_secs = env::var("REQUEST_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS);
+
+        Ok(Self {
+            model,
+            ollama_host,
+            max_search_results,
+            session_db_path,
+            max_fetch,
+            embedding_model,
+            num_ctx,
+            temperature,
+            top_p,
+            request_timeout_secs,
+        })
+    }
+
+    /// Sanity-check the configuration before we hand it to the agent.
+    pub fn validate(&self) -> Result<()> {
+        if self.model.trim().is_empty() {
+            bail!("model name cannot be empty");
+        }
+        if self.ollama_host.trim().is_empty() {
+            bail!("Ollama host cannot be empty");
+        }
+        if self.max_search_results == 0 {
+            bail!("max_search_results must be at least 1");
+        }
+        if self.session_db_path.trim().is_empty() {
+            bail!("session_db_path cannot be empty");
+        }
+        if self.max_fetch == 0 {
+            bail!("max_fetch must be at least 1");
+        }
+        if self.embedding_model.trim().is_empty() {
+            bail!("embedding_model cannot be empty");
+        }
+        if self.num_ctx == 0 {
+            bail!("num_ctx must be at least 1");
+        }
+        if !(0.0..=2.0).contains(&self.temperature) {
+            bail!("temperature must be between 0.0 and 2.0");
+        }
+        if !(0.0..=1.0).contains(&self.top_p) {
+            bail!("top_p must be between 0.0 and 1.0");
+        }
+        if self.request_timeout_secs == 0 {
+            bail!("request_timeout_secs must be at least 1");
+        }
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            model: DEFAULT_MODEL.to_string(),
+            ollama_host: DEFAULT_OLLAMA_HOST.to_string(),
+            max_search_results: DEFAULT_MAX_SEARCH_RESULTS,
+            session_db_path: DEFAULT_SESSION_DB_PATH.to_string(),
+            max_fetch: DEFAULT_MAX_FETCH,
+            embedding_model: DEFAULT_EMBEDDING_MODEL.to_string(),
+            num_ctx: DEFAULT_NUM_CTX,
+            temperature: DEFAULT_TEMPERATURE,
+            top_p: DEFAULT_TOP_P,
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert_eq!(config.model, "llama3.2");
+        assert_eq!(config.max_search_results, 5);
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_model() {
+        let mut config = Config::default();
+        config.model = "".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_accepts_defaults() {
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_temperature() {
+        let mut config = Config::default();
+        config.temperature = 3.0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_top_p() {
+        let mut config = Config::default();
+        config.top_p = 1.5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_timeout() {
+        let mut config = Config::default();
+        config.request_timeout_secs = 0;
+        assert!(config.validate().is_err());
+    }
+}
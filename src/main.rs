@@ -4,6 +4,12 @@ mod config;
 /// Research agent implementation
 mod agent;
 
+/// Embedding-based RAG retrieval over fetched pages
+mod retrieval;
+
+/// SQLite-backed conversation session store
+mod session;
+
 /// Web search and other tools
 mod tools;
 
@@ -87,6 +93,97 @@ struct Args {
         default_value = "false"
     )]
     verbose: bool,
+
+    /// Resume or create a specific conversation session
+    #[arg(
+        long = "session",
+        help = "Attach to a specific session id, creating it if needed",
+        value_name = "ID"
+    )]
+    session: Option<String>,
+
+    /// Resume the most recently created session instead of starting a new one
+    #[arg(
+        long = "resume",
+        help = "Resume the most recently created session",
+        default_value = "false"
+    )]
+    resume: bool,
+
+    /// Max number of search results to fetch the full page content for
+    #[arg(
+        long = "max-fetch",
+        help = "Max number of search results to fetch full page content for"
+    )]
+    max_fetch: Option<usize>,
+
+    /// The Ollama model used to embed chunks/queries for RAG retrieval
+    #[arg(
+        long = "embedding-model",
+        help = "Ollama model to use for RAG embeddings",
+        env = "EMBEDDING_MODEL"
+    )]
+    embedding_model: Option<String>,
+
+    /// Context window size passed to Ollama as `num_ctx`
+    #[arg(long = "num-ctx", help = "Context window size (num_ctx) to request from Ollama")]
+    num_ctx: Option<u32>,
+
+    /// Sampling temperature passed to Ollama
+    #[arg(long = "temperature", help = "Sampling temperature (0.0-2.0)")]
+    temperature: Option<f32>,
+
+    /// Nucleus sampling threshold passed to Ollama
+    #[arg(long = "top-p", help = "Nucleus sampling threshold (0.0-1.0)")]
+    top_p: Option<f32>,
+
+    /// How long to wait for a generation request before giving up
+    #[arg(
+        long = "timeout",
+        help = "Request timeout in seconds, covers a slow first-token model load",
+        value_name = "SECONDS"
+    )]
+    timeout: Option<u64>,
+
+    /// Stream tokens to stdout as they arrive (default: on)
+    #[arg(
+        long = "stream",
+        help = "Stream tokens to stdout as they arrive",
+        default_value = "true"
+    )]
+    stream: bool,
+
+    /// Disable token streaming and buffer the full response instead
+    #[arg(
+        long = "no-stream",
+        help = "Disable token streaming (buffer the full response)",
+        default_value = "false"
+    )]
+    no_stream: bool,
+
+    /// List all stored conversation sessions and exit
+    ///
+    /// A plain flag rather than a `sessions` subcommand, since clap treats a
+    /// positional `query` and a subcommand as mutually exclusive and would
+    /// silently swallow a literal query of "sessions" as this command
+    /// instead of researching that topic.
+    #[arg(
+        long = "sessions",
+        help = "List all stored conversation sessions and exit",
+        default_value = "false"
+    )]
+    sessions: bool,
+
+    /// List models available on the configured Ollama server and exit
+    ///
+    /// Same reasoning as `sessions` above: a flag so a query of "models"
+    /// is never mistaken for this command.
+    #[arg(
+        long = "list-models",
+        help = "List models available on the configured Ollama server and exit",
+        default_value = "false"
+    )]
+    list_models: bool,
 }
 
 #[tokio::main]
@@ -113,32 +210,119 @@ async fn main() -> Result<()> {
         config.model = model;
     }
 
+    if let Some(max_fetch) = args.max_fetch {
+        config.max_fetch = max_fetch;
+    }
+
+    if let Some(embedding_model) = args.embedding_model {
+        config.embedding_model = embedding_model;
+    }
+
+    if let Some(num_ctx) = args.num_ctx {
+        config.num_ctx = num_ctx;
+    }
+
+    if let Some(temperature) = args.temperature {
+        config.temperature = temperature;
+    }
+
+    if let Some(top_p) = args.top_p {
+        config.top_p = top_p;
+    }
+
+    if let Some(timeout) = args.timeout {
+        config.request_timeout_secs = timeout;
+    }
+
     // Validate configuration
     config.validate()?;
 
     info!(
         model = %config.model,
         host = %config.ollama_host,
+        num_ctx = config.num_ctx,
+        temperature = config.temperature,
+        top_p = config.top_p,
+        request_timeout_secs = config.request_timeout_secs,
         "Configuration loaded"
     );
 
-    // Create the research agent
-    let mut agent = ResearchAgent::new(config);
+    // Create the research agent, attached to the requested (or resumed) session
+    let mut agent = ResearchAgent::new(config, args.session, args.resume)?;
+
+    // `--sessions` just lists stored sessions and exits
+    if args.sessions {
+        let sessions = agent.list_sessions()?;
+        if sessions.is_empty() {
+            println!("No sessions found.");
+        } else {
+            println!("{:<38} {:<16} {}", "ID", "MODEL", "TITLE");
+            for s in sessions {
+                println!("{:<38} {:<16} {}", s.id, s.model, s.title);
+            }
+        }
+        return Ok(());
+    }
+
+    // `--list-models` lists what's installed on the Ollama server and exits
+    if args.list_models {
+        let models = agent.list_models().await?;
+        if models.is_empty() {
+            println!("No models found on {}.", agent.ollama_host());
+            println!("Pull one with: ollama pull {}", agent.config_model());
+        } else {
+            println!("Models available on {}:", agent.ollama_host());
+            for model in models {
+                println!("  {}", model);
+            }
+        }
+        return Ok(());
+    }
+
+    info!(session_id = %agent.session_id(), "Attached to session");
+
+    // --no-stream always wins over --stream, so `--stream --no-stream` disables streaming
+    let stream = args.stream && !args.no_stream;
+
+    // quick_search() is pure web search and never touches Ollama, so skip
+    // the connectivity/model check when it's the only thing about to run -
+    // requiring it there would make --quick fail for no reason when Ollama
+    // is stopped or the model isn't pulled. Every other path ends up asking
+    // the model something, so it still needs the check.
+    let needs_connection_check = args.interactive || (!args.quick && args.query.is_some());
+    if needs_connection_check {
+        // Verify Ollama is reachable and the configured model is installed
+        // before doing any real work - this is also our connectivity health
+        // check, since Ollama exposes no dedicated health-check endpoint.
+        if let Err(e) = agent.check_connection().await {
+            eprintln!("\n❌ {}", e);
+            return Err(e);
+        }
+    }
 
     // Check if interactive mode or single query
     if args.interactive {
-        run_interactive(&mut agent).await?;
+        run_interactive(&mut agent, stream, args.verbose).await?;
     } else if let Some(query) = args.query {
-        // Execute the query
-        let result = if args.quick {
+        if args.quick {
             info!("Running in quick search mode");
-            agent.quick_search(&query).await
+            handle_result(agent.quick_search(&query).await)?;
+        } else if stream {
+            info!("Running full research mode (streaming)");
+            println!("\n{}", "=".repeat(60));
+            println!("RESEARCH RESULTS");
+            println!("{}\n", "=".repeat(60));
+
+            if let Err(e) = agent.chat_stream(&query, args.verbose).await {
+                error!(error = %e, "Research failed");
+                eprintln!("\n❌ Research failed: {}", e);
+                return Err(e);
+            }
+            println!("\n{}", "=".repeat(60));
         } else {
             info!("Running full research mode");
-            agent.chat(&query).await
-        };
-
-        handle_result(result)?;
+            handle_result(agent.chat(&query).await)?;
+        }
     } else {
         // No query provided and not interactive - show help
         eprintln!("Error: Please provide a query or use --interactive mode");
@@ -154,7 +338,7 @@ async fn main() -> Result<()> {
 }
 
 /// Run interactive REPL mode
-async fn run_interactive(agent: &mut ResearchAgent) -> Result<()> {
+async fn run_interactive(agent: &mut ResearchAgent, stream: bool, verbose: bool) -> Result<()> {
     println!("\n{}", "=".repeat(60));
     println!("AI Research Agent - Interactive Mode");
     println!("{}", "=".repeat(60));
@@ -185,20 +369,37 @@ async fn run_interactive(agent: &mut ResearchAgent) -> Result<()> {
             _ => {}
         }
 
-        // Process the question
-        match agent.chat(input).await {
-            Ok(response) => {
-                println!("\n{}", "=".repeat(60));
-                println!("AI:");
-                println!("{}", response);
-                println!("{}\n", "=".repeat(60));
+        // Process the question, streaming tokens into this same You:/AI: loop
+        // rather than buffering the whole response, unless streaming is off
+        if stream {
+            println!("\n{}", "=".repeat(60));
+            print!("AI:\n");
+            io::stdout().flush()?;
+
+            match agent.chat_stream(input, verbose).await {
+                Ok(_) => println!("{}\n", "=".repeat(60)),
+                Err(e) => {
+                    eprintln!("\n❌ Error: {}", e);
+                    if let Err(conn_err) = agent.check_connection().await {
+                        eprintln!("💡 {}", conn_err);
+                    }
+                }
             }
-            Err(e) => {
-                eprintln!("\n‚ùå Error: {}", e);
-                if e.to_string().contains("connection refused") {
-                    eprintln!("üí° Tip: Make sure Ollama is running (ollama serve)");
-                } else if e.to_string().contains("model") {
-                    eprintln!("üí° Tip: Make sure the model is installed (ollama pull llama3.2)");
+        } else {
+            match agent.chat(input).await {
+                Ok(response) => {
+                    println!("\n{}", "=".repeat(60));
+                    println!("AI:");
+                    println!("{}", response);
+                    println!("{}\n", "=".repeat(60));
+                }
+                Err(e) => {
+                    eprintln!("\n❌ Error: {}", e);
+                    // Re-probe rather than guessing the cause from the error text -
+                    // this reports the real problem (host down vs. model missing).
+                    if let Err(conn_err) = agent.check_connection().await {
+                        eprintln!("💡 {}", conn_err);
+                    }
                 }
             }
         }
@@ -219,15 +420,7 @@ fn handle_result(result: Result<String>) -> Result<()> {
         }
         Err(e) => {
             error!(error = %e, "Research failed");
-            eprintln!("\n‚ùå Research failed: {}", e);
-
-            if e.to_string().contains("connection refused") {
-                eprintln!("\nüí° Tip: Make sure Ollama is running:");
-                eprintln!("   ollama serve");
-            } else if e.to_string().contains("model") {
-                eprintln!("\nüí° Tip: Make sure the model is installed:");
-                eprintln!("   ollama pull llama3.2");
-            }
+            eprintln!("\n❌ Research failed: {}", e);
             return Err(e);
         }
     }
@@ -282,4 +475,84 @@ mod tests {
         assert!(args.verbose);
         assert_eq!(args.model, Some("llama3.2".to_string()));
     }
+
+    #[test]
+    fn test_args_session_flags() {
+        let args = Args::parse_from(["test", "--session", "abc-123", "--resume", "Test query"]);
+
+        assert_eq!(args.session, Some("abc-123".to_string()));
+        assert!(args.resume);
+    }
+
+    #[test]
+    fn test_args_max_fetch_flag() {
+        let args = Args::parse_from(["test", "--max-fetch", "5", "Test query"]);
+        assert_eq!(args.max_fetch, Some(5));
+    }
+
+    #[test]
+    fn test_args_embedding_model_flag() {
+        let args = Args::parse_from(["test", "--embedding-model", "mxbai-embed-large", "Test query"]);
+        assert_eq!(args.embedding_model, Some("mxbai-embed-large".to_string()));
+    }
+
+    #[test]
+    fn test_args_generation_option_flags() {
+        let args = Args::parse_from([
+            "test",
+            "--temperature",
+            "0.2",
+            "--num-ctx",
+            "8192",
+            "--top-p",
+            "0.95",
+            "--timeout",
+            "30",
+            "Test query",
+        ]);
+
+        assert_eq!(args.temperature, Some(0.2));
+        assert_eq!(args.num_ctx, Some(8192));
+        assert_eq!(args.top_p, Some(0.95));
+        assert_eq!(args.timeout, Some(30));
+    }
+
+    #[test]
+    fn test_args_stream_flags() {
+        let args = Args::parse_from(["test", "Test query"]);
+        assert!(args.stream);
+        assert!(!args.no_stream);
+
+        let args = Args::parse_from(["test", "--no-stream", "Test query"]);
+        assert!(args.stream);
+        assert!(args.no_stream);
+    }
+
+    #[test]
+    fn test_args_sessions_flag() {
+        let args = Args::parse_from(["test", "--sessions"]);
+        assert!(args.sessions);
+        assert_eq!(args.query, None);
+    }
+
+    #[test]
+    fn test_args_list_models_flag() {
+        let args = Args::parse_from(["test", "--list-models"]);
+        assert!(args.list_models);
+        assert_eq!(args.query, None);
+    }
+
+    #[test]
+    fn test_args_query_matching_flag_name_is_not_swallowed() {
+        // A literal query of "sessions" or "models" must be treated as a
+        // query, not misread as one of the flags above - this is the whole
+        // reason they're flags instead of a `sessions`/`models` subcommand.
+        let args = Args::parse_from(["test", "sessions"]);
+        assert_eq!(args.query, Some("sessions".to_string()));
+        assert!(!args.sessions);
+
+        let args = Args::parse_from(["test", "models"]);
+        assert_eq!(args.query, Some("models".to_string()));
+        assert!(!args.list_models);
+    }
 }
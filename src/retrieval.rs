@@ -0,0 +1,211 @@
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+/// Target size (in words, used as a cheap proxy for tokens) of each chunk.
+const CHUNK_WORDS: usize = 500;
+
+/// Overlap (in words) between consecutive chunks, so a fact that falls on a
+/// chunk boundary still appears whole in at least one chunk.
+const CHUNK_OVERLAP_WORDS: usize = 50;
+
+/// Split `text` into overlapping ~`CHUNK_WORDS`-word windows.
+pub fn chunk_text(text: &str) -> Vec<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let stride = CHUNK_WORDS.saturating_sub(CHUNK_OVERLAP_WORDS).max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let end = (start + CHUNK_WORDS).min(words.len());
+        chunks.push(words[start..end].join(" "));
+
+        if end == words.len() {
+            break;
+        }
+        start += stride;
+    }
+
+    chunks
+}
+
+/// A chunk of source text together with the embedding used to rank it.
+#[derive(Debug, Clone)]
+pub struct EmbeddedChunk {
+    pub text: String,
+    pub source_url: String,
+    pub embedding: Vec<f32>,
+}
+
+/// Calls Ollama's `/api/embeddings` endpoint for a configured embedding model.
+#[derive(Debug, Clone)]
+pub struct OllamaEmbeddingClient {
+    host: String,
+    model: String,
+    client: reqwest::Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    embedding: Vec<f32>,
+}
+
+impl OllamaEmbeddingClient {
+    /// `request_timeout_secs` bounds each individual embedding call, so a
+    /// stalled Ollama embeddings request can't hang the agent indefinitely.
+    pub fn new(host: impl Into<String>, model: impl Into<String>, request_timeout_secs: u64) -> Self {
+        Self {
+            host: host.into(),
+            model: model.into(),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(request_timeout_secs))
+                .build()
+                .expect("building the HTTP client should not fail"),
+        }
+    }
+
+    /// Embed a single piece of text.
+    pub async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let url = format!("{}/api/embeddings", self.host.trim_end_matches('/'));
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&json!({ "model": self.model, "prompt": text }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: EmbeddingResponse = response.json().await?;
+        Ok(body.embedding)
+    }
+}
+
+/// Cosine similarity between two vectors. Returns 0.0 for mismatched,
+/// empty or zero-norm inputs rather than panicking, so such a chunk scores
+/// the same as one orthogonal to the query.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.is_empty() || b.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (norm_a * norm_b)
+}
+
+/// Storage for embedded chunks, abstracted behind a trait so an in-memory
+/// store can later be swapped for a persistent cache without touching
+/// callers.
+pub trait VectorStore {
+    fn add(&mut self, chunk: EmbeddedChunk);
+
+    /// Return the top-`k` chunks ranked by cosine similarity to `query_embedding`.
+    fn top_k(&self, query_embedding: &[f32], k: usize) -> Vec<EmbeddedChunk>;
+}
+
+/// A simple `Vec`-backed `VectorStore` that scores every chunk on each query.
+/// Fine for the handful of pages pulled per research query; a persistent
+/// cache can implement the same trait later if that stops being true.
+#[derive(Debug, Default)]
+pub struct InMemoryVectorStore {
+    chunks: Vec<EmbeddedChunk>,
+}
+
+impl InMemoryVectorStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl VectorStore for InMemoryVectorStore {
+    fn add(&mut self, chunk: EmbeddedChunk) {
+        self.chunks.push(chunk);
+    }
+
+    fn top_k(&self, query_embedding: &[f32], k: usize) -> Vec<EmbeddedChunk> {
+        let mut scored: Vec<(f32, &EmbeddedChunk)> = self
+            .chunks
+            .iter()
+            .map(|c| (cosine_similarity(query_embedding, &c.embedding), c))
+            .collect();
+
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        scored.into_iter().take(k).map(|(_, c)| c.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_text_empty() {
+        assert!(chunk_text("").is_empty());
+    }
+
+    #[test]
+    fn test_chunk_text_short_text_single_chunk() {
+        let chunks = chunk_text("just a few words here");
+        assert_eq!(chunks, vec!["just a few words here".to_string()]);
+    }
+
+    #[test]
+    fn test_chunk_text_overlaps() {
+        let text = (0..1000).map(|i| i.to_string()).collect::<Vec<_>>().join(" ");
+        let chunks = chunk_text(&text);
+
+        assert!(chunks.len() > 1);
+        // The second chunk should start before the first chunk ends.
+        let first_words: Vec<&str> = chunks[0].split_whitespace().collect();
+        let second_words: Vec<&str> = chunks[1].split_whitespace().collect();
+        assert_eq!(second_words[0], first_words[CHUNK_WORDS - CHUNK_OVERLAP_WORDS]);
+    }
+
+    #[test]
+    fn test_cosine_similarity_identical_vectors() {
+        let v = vec![1.0, 2.0, 3.0];
+        assert!((cosine_similarity(&v, &v) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cosine_similarity_orthogonal_vectors() {
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_cosine_similarity_mismatched_lengths() {
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_vector_store_top_k_ranks_by_similarity() {
+        let mut store = InMemoryVectorStore::new();
+        store.add(EmbeddedChunk {
+            text: "close".to_string(),
+            source_url: "https://a.example".to_string(),
+            embedding: vec![1.0, 0.0],
+        });
+        store.add(EmbeddedChunk {
+            text: "far".to_string(),
+            source_url: "https://b.example".to_string(),
+            embedding: vec![0.0, 1.0],
+        });
+
+        let top = store.top_k(&[1.0, 0.0], 1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].text, "close");
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{params, Connection};
+
+/// Summary row from the `sessions` table, as shown by the `--sessions` flag.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub model: String,
+}
+
+/// SQLite-backed store for conversation sessions and their messages.
+///
+/// Schema:
+/// - `sessions(id, title, created_at, model)`
+/// - `messages(id, session_id, turn_index, role, content, timestamp)`
+///
+/// Each user/AI exchange is written as two rows in `messages` (one with
+/// `role = "user"`, one with `role = "assistant"`) sharing the same
+/// `turn_index`, which lets us reconstruct ordered `(question, answer)`
+/// pairs for the `{history}` block in `CHAT_SYSTEM_PROMPT`.
+pub struct SessionStore {
+    conn: Connection,
+}
+
+impl SessionStore {
+    /// Open (creating if necessary) the SQLite database at `path` and
+    /// ensure the schema exists.
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sessions (
+                id         TEXT PRIMARY KEY,
+                title      TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                model      TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS messages (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                turn_index INTEGER NOT NULL,
+                role       TEXT NOT NULL,
+                content    TEXT NOT NULL,
+                timestamp  TEXT NOT NULL,
+                FOREIGN KEY(session_id) REFERENCES sessions(id)
+            );",
+        )?;
+
+        Ok(Self { conn })
+    }
+
+    /// Ensure a session row exists, creating one with `title` if it doesn't.
+    /// Callers are expected to call this once they have a real title to
+    /// give it (e.g. derived from the session's first query), rather than
+    /// eagerly at session construction, so code paths that never actually
+    /// chat don't leave a row behind.
+    pub fn ensure_session(&self, id: &str, title: &str, model: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO sessions (id, title, created_at, model) VALUES (?1, ?2, ?3, ?4)",
+            params![id, title, Utc::now().to_rfc3339(), model],
+        )?;
+        Ok(())
+    }
+
+    /// List all stored sessions, most recently created first.
+    pub fn list_sessions(&self) -> Result<Vec<SessionSummary>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, title, created_at, model FROM sessions ORDER BY created_at DESC")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SessionSummary {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                created_at: row.get(2)?,
+                model: row.get(3)?,
+            })
+        })?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Return the id of the most recently created session, if any.
+    pub fn latest_session_id(&self) -> Result<Option<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM sessions ORDER BY created_at DESC LIMIT 1")?;
+        let mut rows = stmt.query([])?;
+        Ok(match rows.next()? {
+            Some(row) => Some(row.get(0)?),
+            None => None,
+        })
+    }
+
+    /// Load all turns for a session as `(user_question, ai_response)` pairs,
+    /// ordered by `turn_index`, then by row id within a turn so the `user`
+    /// row is always seen before its `assistant` row regardless of role name.
+    pub fn load_turns(&self, session_id: &str) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT turn_index, role, content FROM messages
+             WHERE session_id = ?1 ORDER BY turn_index ASC, id ASC",
+        )?;
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut turns: Vec<(String, String)> = Vec::new();
+        let mut pending_question: Option<String> = None;
+
+        for row in rows {
+            let (_, role, content) = row?;
+            match role.as_str() {
+                "user" => pending_question = Some(content),
+                "assistant" => {
+                    if let Some(question) = pending_question.take() {
+                        turns.push((question, content));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(turns)
+    }
+
+    /// Persist one chat turn as a `user` row followed by an `assistant` row.
+    pub fn append_turn(
+        &self,
+        session_id: &str,
+        turn_index: usize,
+        question: &str,
+        answer: &str,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO messages (session_id, turn_index, role, content, timestamp)
+             VALUES (?1, ?2, 'user', ?3, ?4)",
+            params![session_id, turn_index as i64, question, now],
+        )?;
+        self.conn.execute(
+            "INSERT INTO messages (session_id, turn_index, role, content, timestamp)
+             VALUES (?1, ?2, 'assistant', ?3, ?4)",
+            params![session_id, turn_index as i64, answer, now],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_memory_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (id TEXT PRIMARY KEY, title TEXT NOT NULL, created_at TEXT NOT NULL, model TEXT NOT NULL);
+             CREATE TABLE messages (id INTEGER PRIMARY KEY AUTOINCREMENT, session_id TEXT NOT NULL, turn_index INTEGER NOT NULL, role TEXT NOT NULL, content TEXT NOT NULL, timestamp TEXT NOT NULL);",
+        )
+        .unwrap();
+        SessionStore { conn }
+    }
+
+    #[test]
+    fn test_ensure_and_list_sessions() {
+        let store = in_memory_store();
+        store.ensure_session("abc", "My session", "llama3.2").unwrap();
+        let sessions = store.list_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, "abc");
+    }
+
+    #[test]
+    fn test_append_and_load_turns() {
+        let store = in_memory_store();
+        store.ensure_session("abc", "My session", "llama3.2").unwrap();
+        store.append_turn("abc", 0, "hello", "hi there").unwrap();
+        store.append_turn("abc", 1, "how are you", "good").unwrap();
+
+        let turns = store.load_turns("abc").unwrap();
+        assert_eq!(
+            turns,
+            vec![
+                ("hello".to_string(), "hi there".to_string()),
+                ("how are you".to_string(), "good".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_latest_session_id_empty() {
+        let store = in_memory_store();
+        assert_eq!(store.latest_session_id().unwrap(), None);
+    }
+}
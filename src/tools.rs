@@ -0,0 +1,286 @@
+use anyhow::Result;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Pages with fewer extracted words than this are treated as a redirect,
+/// paywall, or nav-only shell rather than real content.
+const MIN_SUBSTANTIVE_WORDS: usize = 100;
+
+/// A single organic search result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub title: String,
+    pub snippet: String,
+    pub url: String,
+}
+
+/// Searches the web via a public SearxNG instance and returns the top results.
+#[derive(Debug, Clone)]
+pub struct WebSearchTool {
+    max_results: usize,
+    client: reqwest::Client,
+}
+
+impl WebSearchTool {
+    /// `request_timeout_secs` bounds each individual search request, so a
+    /// stalled connection can't hang the agent indefinitely.
+    pub fn new(max_results: usize, request_timeout_secs: u64) -> Self {
+        Self {
+            max_results,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(request_timeout_secs))
+                .build()
+                .expect("building the HTTP client should not fail"),
+        }
+    }
+
+    /// Run a search and return up to `max_results` hits.
+    pub async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        debug!(query, "Running web search");
+
+        let response = self
+            .client
+            .get("https://searx.be/search")
+            .query(&[("q", query), ("format", "json")])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: serde_json::Value = response.json().await?;
+
+        let results = body["results"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|r| {
+                Some(SearchResult {
+                    title: r["title"].as_str()?.to_string(),
+                    snippet: r["content"].as_str().unwrap_or_default().to_string(),
+                    url: r["url"].as_str()?.to_string(),
+                })
+            })
+            .take(self.max_results)
+            .collect();
+
+        Ok(results)
+    }
+}
+
+/// Arguments the model supplies when it calls `web_search`.
+#[derive(Debug, Deserialize)]
+pub struct WebSearchArgs {
+    query: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebSearchError {
+    #[error("search request failed: {0}")]
+    Request(#[from] anyhow::Error),
+}
+
+impl Tool for WebSearchTool {
+    const NAME: &'static str = "web_search";
+
+    type Error = WebSearchError;
+    type Args = WebSearchArgs;
+    type Output = Vec<SearchResult>;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Search the web for up-to-date information on a topic.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "The search query"
+                    }
+                },
+                "required": ["query"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        self.search(&args.query).await.map_err(WebSearchError::Request)
+    }
+}
+
+/// A page that was fetched and reduced to its visible, readable text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchedPage {
+    pub url: String,
+    pub text: String,
+}
+
+/// Downloads a web page and strips it down to its visible text, so the
+/// agent can synthesize from real content instead of just a search
+/// snippet.
+#[derive(Debug, Clone)]
+pub struct PageFetchTool {
+    client: reqwest::Client,
+}
+
+impl PageFetchTool {
+    /// `request_timeout_secs` bounds each individual page fetch, so a slow
+    /// or unresponsive page can't hang the agent indefinitely.
+    pub fn new(request_timeout_secs: u64) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(request_timeout_secs))
+                .build()
+                .expect("building the HTTP client should not fail"),
+        }
+    }
+
+    /// Fetch a single page and extract its readable text.
+    pub async fn fetch(&self, url: &str) -> Result<FetchedPage> {
+        debug!(url, "Fetching page");
+
+        let html = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        Ok(FetchedPage {
+            url: url.to_string(),
+            text: Self::extract_text(&html),
+        })
+    }
+
+    /// Fetch `candidate_urls` in order, skipping any page whose extracted
+    /// body falls under `MIN_SUBSTANTIVE_WORDS` words, until `max_pages`
+    /// substantive documents have been collected or the candidates are
+    /// exhausted.
+    pub async fn fetch_substantive(
+        &self,
+        candidate_urls: &[String],
+        max_pages: usize,
+    ) -> Vec<FetchedPage> {
+        let mut pages = Vec::new();
+
+        for url in candidate_urls {
+            if pages.len() >= max_pages {
+                break;
+            }
+
+            match self.fetch(url).await {
+                Ok(page) if page.text.split_whitespace().count() >= MIN_SUBSTANTIVE_WORDS => {
+                    pages.push(page);
+                }
+                Ok(_) => debug!(url, "Skipping thin page (likely redirect/paywall/nav shell)"),
+                Err(e) => warn!(url, error = %e, "Failed to fetch page"),
+            }
+        }
+
+        pages
+    }
+
+    /// Strip HTML down to the text a reader would actually see, skipping
+    /// `<script>`/`<style>` contents which aren't rendered.
+    fn extract_text(html: &str) -> String {
+        let document = Html::parse_document(html);
+        let body_selector = Selector::parse("body").expect("static selector is valid");
+
+        let mut words = Vec::new();
+        for body in document.select(&body_selector) {
+            Self::collect_visible_text(body, &mut words);
+        }
+
+        words.join(" ")
+    }
+
+    fn collect_visible_text(element: scraper::ElementRef, out: &mut Vec<String>) {
+        for child in element.children() {
+            if let Some(child_el) = scraper::ElementRef::wrap(child) {
+                let tag = child_el.value().name();
+                if tag == "script" || tag == "style" {
+                    continue;
+                }
+                Self::collect_visible_text(child_el, out);
+            } else if let Some(text) = child.value().as_text() {
+                out.extend(text.split_whitespace().map(str::to_string));
+            }
+        }
+    }
+}
+
+/// Arguments the model supplies when it calls `fetch_page`.
+#[derive(Debug, Deserialize)]
+pub struct PageFetchArgs {
+    url: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PageFetchError {
+    #[error("page fetch failed: {0}")]
+    Request(#[from] anyhow::Error),
+}
+
+impl Tool for PageFetchTool {
+    const NAME: &'static str = "fetch_page";
+
+    type Error = PageFetchError;
+    type Args = PageFetchArgs;
+    type Output = FetchedPage;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Fetch a web page by URL and return its readable text content."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "url": {
+                        "type": "string",
+                        "description": "The URL to fetch, typically one returned by web_search"
+                    }
+                },
+                "required": ["url"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        self.fetch(&args.url).await.map_err(PageFetchError::Request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_web_search_tool_new() {
+        let tool = WebSearchTool::new(3, 30);
+        assert_eq!(tool.max_results, 3);
+    }
+
+    #[test]
+    fn test_extract_text_strips_tags() {
+        let html = "<html><body><h1>Title</h1><p>Some   body text.</p></body></html>";
+        let text = PageFetchTool::extract_text(html);
+        assert_eq!(text, "Title Some body text.");
+    }
+
+    #[test]
+    fn test_extract_text_ignores_script_and_style_content() {
+        let html = "<body><script>var x = 1;</script><style>p { color: red; }</style><p>Real content here.</p></body>";
+        let text = PageFetchTool::extract_text(html);
+        assert!(text.contains("Real content here."));
+        assert!(!text.contains("var x"));
+        assert!(!text.contains("color"));
+    }
+}